@@ -0,0 +1 @@
+pub mod std_links;
@@ -7,11 +7,12 @@ use once_cell::sync::Lazy;
 use pulldown_cmark::{BrokenLink, CowStr, Event, LinkType, Options, Parser, Tag};
 use regex::Regex;
 use std::collections::HashMap;
+use std::collections::HashSet;
 use std::fmt::Write as _;
 use std::fs;
 use std::io::{self, Write as _};
 use std::ops::Range;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::{self, Command};
 use tempfile::TempDir;
 
@@ -34,6 +35,7 @@ static MD_LINK_SHORTCUT: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?s)(\[.+\])").
 pub fn std_links(book: &mut Book) {
     // Collect all links in all chapters.
     let mut chapter_links = HashMap::new();
+    let mut chapter_contents = HashMap::new();
     for item in book.iter() {
         let BookItem::Chapter(ch) = item else {
             continue;
@@ -42,37 +44,61 @@ pub fn std_links(book: &mut Book) {
             continue;
         }
         let key = ch.source_path.as_ref().unwrap();
-        chapter_links.insert(key, collect_markdown_links(&ch));
+        let (links, _) = collect_markdown_links(&ch, false);
+        chapter_links.insert(key, links);
+        chapter_contents.insert(key, ch.content.as_str());
     }
-    // Write a Rust source file to use with rustdoc to generate intra-doc links.
-    let tmp = TempDir::with_prefix("mdbook-spec-").unwrap();
-    run_rustdoc(&tmp, &chapter_links);
 
-    // Extract the links from the generated html.
-    let generated =
-        fs::read_to_string(tmp.path().join("doc/a/index.html")).expect("index.html generated");
-    let mut urls: Vec<_> = STD_LINK_EXTRACT_RE
-        .captures_iter(&generated)
-        .map(|cap| cap.get(1).unwrap().as_str())
-        .collect();
-    let mut urls = &mut urls[..];
-    let expected_len: usize = chapter_links.values().map(|l| l.len()).sum();
-    if urls.len() != expected_len {
-        eprintln!(
-            "error: expected rustdoc to generate {} links, but found {}",
-            expected_len,
-            urls.len(),
-        );
-        process::exit(1);
+    let rustdoc = std::env::var("RUSTDOC").unwrap_or_else(|_| "rustdoc".into());
+    let rustc_version = rustdoc_version(&rustdoc);
+    let cache_dir = link_cache_dir();
+    let mut resolved = load_link_cache(&cache_dir, &rustc_version);
+
+    // Distinct chapters frequently reference the same std items (`Option`,
+    // `Vec`, `Result`), and the cache means most links don't need rustdoc
+    // at all. Only the links that are neither cached nor already seen
+    // this run need to be sent through rustdoc, and each distinct
+    // `dest_url` only needs to be sent once.
+    let mut residual: HashMap<&str, (&PathBuf, &Link<'_>)> = HashMap::new();
+    for (ch_path, links) in &chapter_links {
+        for link in links {
+            if !resolved.contains_key(link.dest_url.as_ref()) {
+                residual
+                    .entry(link.dest_url.as_ref())
+                    .or_insert((*ch_path, link));
+            }
+        }
+    }
+
+    if !residual.is_empty() {
+        let residual: Vec<_> = residual
+            .into_iter()
+            .map(|(dest_url, (ch_path, link))| (dest_url, ch_path, link))
+            .collect();
+        // Write a Rust source file to use with rustdoc to generate intra-doc links.
+        let tmp = TempDir::with_prefix("mdbook-spec-").unwrap();
+        let newly_resolved = run_rustdoc(&tmp, &rustdoc, &residual, &chapter_contents);
+        resolved.extend(newly_resolved);
+        save_link_cache(&cache_dir, &rustc_version, &resolved);
     }
-    // Unflatten the urls list so that it is split back by chapter.
-    let mut ch_urls: HashMap<&PathBuf, Vec<_>> = HashMap::new();
+
+    // Look up every link's resolved URL (from the cache or from the
+    // rustdoc run above) to rebuild the per-chapter list expected by the
+    // rest of this function.
+    let mut ch_urls: HashMap<&PathBuf, Vec<String>> = HashMap::new();
     for (ch_path, links) in &chapter_links {
-        let xs;
-        (xs, urls) = urls.split_at_mut(links.len());
-        ch_urls.insert(ch_path, xs.into());
+        let urls = links
+            .iter()
+            .map(|link| resolved[link.dest_url.as_ref()].clone())
+            .collect();
+        ch_urls.insert(ch_path, urls);
     }
 
+    // If requested, collect a machine-readable report of every resolved
+    // link as we go, to be written out once all chapters are processed.
+    let dump_path = std::env::var_os("SPEC_DUMP_LINKS").map(PathBuf::from);
+    let mut link_dump = Vec::new();
+
     // Do this in two passes to deal with lifetimes.
     let mut ch_contents = HashMap::new();
     for item in book.iter() {
@@ -83,22 +109,53 @@ pub fn std_links(book: &mut Book) {
             continue;
         }
         let key = ch.source_path.as_ref().unwrap();
+
+        if dump_path.is_some() {
+            for (link, resolved_url) in chapter_links[key].iter().zip(&ch_urls[key]) {
+                link_dump.push(LinkDumpEntry {
+                    chapter: key.to_path_buf(),
+                    dest_url: link.dest_url.to_string(),
+                    link_type: format!("{:?}", link.link_type),
+                    resolved_url: resolved_url.clone(),
+                });
+            }
+        }
+
         // Create a list of replacements to make in the raw markdown to point to the new url.
-        let replacements = compute_replacements(&ch.content, &chapter_links[key], &ch_urls[key]);
+        let urls: Vec<&str> = ch_urls[key].iter().map(String::as_str).collect();
+        let replacements = compute_replacements(&ch.content, &chapter_links[key], &urls);
+        // Converting a reference-style link (like `` [`OsString`] `` with
+        // a separate `` [`OsString`]: std::ffi::OsString `` definition)
+        // to an inline link can leave the definition dangling with
+        // nothing left to reference it; find those and remove them too.
+        let orphaned_definitions = orphaned_reference_definitions(&ch.content, &chapter_links[key]);
 
-        let mut new_contents = ch.content.clone();
+        // Apply both sets of edits in a single bottom-up, range-reversed
+        // pass so that earlier edits don't shift the byte offsets of
+        // later ones.
+        let mut edits: Vec<(Range<usize>, String)> = Vec::new();
         for (md_link, url, range) in replacements {
             // Convert links to be relative so that links work offline and
             // with the linkchecker.
             let url = relative_url(url, ch);
-            // Note that this may orphan reference link definitions. This should
-            // probably remove them, but pulldown_cmark doesn't give the span for
-            // the reference definition.
-            new_contents.replace_range(range, &format!("{md_link}({url})"));
+            edits.push((range, format!("{md_link}({url})")));
+        }
+        for range in orphaned_definitions {
+            edits.push((range, String::new()));
+        }
+        edits.sort_by_key(|e| std::cmp::Reverse(e.0.start));
+
+        let mut new_contents = ch.content.clone();
+        for (range, text) in edits {
+            new_contents.replace_range(range, &text);
         }
         ch_contents.insert(key.clone(), new_contents);
     }
 
+    if let Some(dump_path) = dump_path {
+        write_link_dump(&dump_path, &link_dump);
+    }
+
     // Replace the content with the new content.
     book.for_each_mut(|item| {
         let BookItem::Chapter(ch) = item else {
@@ -129,8 +186,14 @@ struct Link<'a> {
     range: Range<usize>,
 }
 
-/// Collects all markdown links that look like they might be standard library links.
-fn collect_markdown_links(chapter: &Chapter) -> Vec<Link<'_>> {
+/// Collects all markdown links that look like they might be standard
+/// library links.
+///
+/// If `collect_bare_paths` is set, this also harvests `Event::Code`
+/// spans (like `` `std::mem::swap` ``) that look like a resolvable std
+/// path but aren't already part of a link, returning them separately;
+/// see [`lint_unlinked_std_paths`].
+fn collect_markdown_links(chapter: &Chapter, collect_bare_paths: bool) -> (Vec<Link<'_>>, Vec<Link<'_>>) {
     let mut opts = Options::empty();
     opts.insert(Options::ENABLE_TABLES);
     opts.insert(Options::ENABLE_FOOTNOTES);
@@ -141,6 +204,7 @@ fn collect_markdown_links(chapter: &Chapter) -> Vec<Link<'_>> {
 
     let mut broken_links = Vec::new();
     let mut links = Vec::new();
+    let mut bare_paths = Vec::new();
 
     // Broken links are collected so that you can write something like
     // `[std::option::Option]` which in pulldown_cmark's eyes is a broken
@@ -191,21 +255,70 @@ fn collect_markdown_links(chapter: &Chapter) -> Vec<Link<'_>> {
                     range: range.clone(),
                 });
             }
+            Event::Code(text) if collect_bare_paths && looks_like_std_path(&text) => {
+                bare_paths.push(Link {
+                    link_type: LinkType::Shortcut,
+                    dest_url: text,
+                    range: range.clone(),
+                });
+            }
             _ => {}
         }
     }
     links.extend(broken_links);
-    links
+    // Drop any candidate that is actually part of a link that was
+    // already collected above (for example the `` `OsString` `` in
+    // `` [`OsString`] ``), since those are already real links.
+    bare_paths.retain(|candidate| {
+        !links
+            .iter()
+            .any(|link| link.range.start <= candidate.range.start && candidate.range.end <= link.range.end)
+    });
+    (links, bare_paths)
+}
+
+/// Regex for a bare `::`-separated path, like `std::mem::swap`.
+static BARE_PATH_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^[A-Za-z_][A-Za-z0-9_]*(::[A-Za-z_][A-Za-z0-9_]*)+$").unwrap());
+
+/// Names of primitive types, which rustdoc can resolve intra-doc links to
+/// even though they aren't `::`-separated paths.
+const PRIMITIVE_NAMES: &[&str] = &[
+    "bool", "char", "str", "f32", "f64", "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16",
+    "u32", "u64", "u128", "usize",
+];
+
+/// Returns whether `text` (the contents of a code span) looks like it
+/// might be a resolvable standard library path, such as
+/// `std::mem::swap` or a bare primitive name like `u8`.
+///
+/// This is only a syntactic heuristic; the actual resolution is left to
+/// rustdoc in [`lint_unlinked_std_paths`].
+fn looks_like_std_path(text: &str) -> bool {
+    let text = text.trim_end_matches("()");
+    BARE_PATH_RE.is_match(text) || PRIMITIVE_NAMES.contains(&text)
 }
 
 /// Generates links using rustdoc.
 ///
-/// This takes the given links and creates a temporary Rust source file
-/// containing those links within doc-comments, and then runs rustdoc to
-/// generate intra-doc links on them.
+/// This takes the given `residual` links (already deduplicated by
+/// `dest_url`, containing only links that weren't found in the
+/// [resolved-link cache](load_link_cache)) and creates a temporary Rust
+/// source file containing those links within doc-comments, then runs
+/// rustdoc to generate intra-doc links on them.
 ///
-/// The output will be in the given `tmp` directory.
-fn run_rustdoc(tmp: &TempDir, chapter_links: &HashMap<&PathBuf, Vec<Link<'_>>>) {
+/// Returns a map from each link's `dest_url` to its resolved, absolute
+/// `doc.rust-lang.org` URL.
+///
+/// `chapter_contents` is used only if rustdoc reports a broken link; it
+/// lets the error be translated from a line in the synthetic `a.rs` back
+/// to the real line/column in the originating chapter's markdown.
+fn run_rustdoc(
+    tmp: &TempDir,
+    rustdoc: &str,
+    residual: &[(&str, &PathBuf, &Link<'_>)],
+    chapter_contents: &HashMap<&PathBuf, &str>,
+) -> HashMap<String, String> {
     let src_path = tmp.path().join("a.rs");
     // Allow redundant since there could some in-scope things that are
     // technically not necessary, but we don't care about (like
@@ -214,25 +327,30 @@ fn run_rustdoc(tmp: &TempDir, chapter_links: &HashMap<&PathBuf, Vec<Link<'_>>>)
         "#![deny(rustdoc::broken_intra_doc_links)]\n\
          #![allow(rustdoc::redundant_explicit_links)]\n"
     );
+    // Maps a 1-indexed line number in the generated `src` to the chapter
+    // and markdown span that the `//! - LINK:` line on that line came
+    // from, so that rustdoc diagnostics (which only know about `a.rs`
+    // line numbers) can be translated back to real source locations.
+    let mut line_map: Vec<(u32, &PathBuf, Range<usize>)> = Vec::new();
     // This uses a list to make easy to pull the links out of the generated HTML.
-    for (_ch_path, links) in chapter_links {
-        for link in links {
-            match link.link_type {
-                LinkType::Inline
-                | LinkType::Reference
-                | LinkType::Collapsed
-                | LinkType::Shortcut => {
-                    writeln!(src, "//! - LINK: [{}]", link.dest_url).unwrap();
-                }
-                LinkType::ReferenceUnknown
-                | LinkType::CollapsedUnknown
-                | LinkType::ShortcutUnknown => {
-                    // These should only happen due to broken link replacements.
-                    panic!("unexpected link type unknown {link:?}");
-                }
-                LinkType::Autolink | LinkType::Email => {
-                    panic!("link type should have been filtered {link:?}");
-                }
+    for &(dest_url, ch_path, link) in residual {
+        match link.link_type {
+            LinkType::Inline
+            | LinkType::Reference
+            | LinkType::Collapsed
+            | LinkType::Shortcut => {
+                writeln!(src, "//! - LINK: [{dest_url}]").unwrap();
+                let line = src.matches('\n').count() as u32;
+                line_map.push((line, ch_path, link.range.clone()));
+            }
+            LinkType::ReferenceUnknown
+            | LinkType::CollapsedUnknown
+            | LinkType::ShortcutUnknown => {
+                // These should only happen due to broken link replacements.
+                panic!("unexpected link type unknown {link:?}");
+            }
+            LinkType::Autolink | LinkType::Email => {
+                panic!("link type should have been filtered {link:?}");
             }
         }
     }
@@ -245,7 +363,6 @@ fn run_rustdoc(tmp: &TempDir, chapter_links: &HashMap<&PathBuf, Vec<Link<'_>>>)
     )
     .unwrap();
     fs::write(&src_path, &src).unwrap();
-    let rustdoc = std::env::var("RUSTDOC").unwrap_or_else(|_| "rustdoc".into());
     let output = Command::new(rustdoc)
         .arg("--edition=2021")
         .arg(&src_path)
@@ -253,10 +370,380 @@ fn run_rustdoc(tmp: &TempDir, chapter_links: &HashMap<&PathBuf, Vec<Link<'_>>>)
         .output()
         .expect("rustdoc installed");
     if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        report_broken_link_diagnostics(&stderr, &line_map, chapter_contents);
         eprintln!("error: failed to extract std links ({:?})\n", output.status,);
         io::stderr().write_all(&output.stderr).unwrap();
         process::exit(1);
     }
+
+    // Extract the links from the generated html.
+    let generated =
+        fs::read_to_string(tmp.path().join("doc/a/index.html")).expect("index.html generated");
+    let urls: Vec<_> = STD_LINK_EXTRACT_RE
+        .captures_iter(&generated)
+        .map(|cap| cap.get(1).unwrap().as_str())
+        .collect();
+    if urls.len() != residual.len() {
+        eprintln!(
+            "error: expected rustdoc to generate {} links, but found {}",
+            residual.len(),
+            urls.len(),
+        );
+        process::exit(1);
+    }
+
+    residual
+        .iter()
+        .zip(urls)
+        .map(|(&(dest_url, _, _), raw_url)| {
+            (dest_url.to_string(), extract_anchor_url(raw_url).to_string())
+        })
+        .collect()
+}
+
+/// Directory used to persist the resolved-link cache between builds, so
+/// that distinct chapters referencing the same std items don't have to
+/// pay for a fresh rustdoc invocation every time. Defaults to `book`
+/// relative to the working directory (mdbook's default build directory;
+/// a configured `build-dir` is not read), but can be overridden with `MDBOOK_SPEC_CACHE_DIR`
+/// (useful when the preprocessor is run outside of `mdbook build`).
+fn link_cache_dir() -> PathBuf {
+    std::env::var_os("MDBOOK_SPEC_CACHE_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("book"))
+}
+
+/// The file name of the resolved-link cache within [`link_cache_dir`].
+const LINK_CACHE_FILE: &str = "mdbook-spec-links-cache.json";
+
+/// The on-disk format of the resolved-link cache.
+///
+/// Tagged with the rustc/rustdoc version string it was generated with,
+/// since a different version of rustc (and thus of the vendored std
+/// docs) may resolve a `dest_url` to a different URL or add new items;
+/// the cache is discarded rather than trusted across a toolchain change.
+#[derive(serde::Serialize, serde::Deserialize, Default)]
+struct LinkCache {
+    rustc_version: String,
+    /// Maps a `dest_url` (such as `std::ffi::OsString`) to its resolved
+    /// absolute `doc.rust-lang.org` URL.
+    links: HashMap<String, String>,
+}
+
+/// Loads the resolved-link cache from `cache_dir`, discarding it if it's
+/// missing, unreadable, or was generated by a different `rustc_version`.
+fn load_link_cache(cache_dir: &Path, rustc_version: &str) -> HashMap<String, String> {
+    let path = cache_dir.join(LINK_CACHE_FILE);
+    let Ok(contents) = fs::read_to_string(path) else {
+        return HashMap::new();
+    };
+    let Ok(cache) = serde_json::from_str::<LinkCache>(&contents) else {
+        return HashMap::new();
+    };
+    if cache.rustc_version != rustc_version {
+        return HashMap::new();
+    }
+    cache.links
+}
+
+/// Persists the merged resolved-link cache to `cache_dir`.
+fn save_link_cache(cache_dir: &Path, rustc_version: &str, links: &HashMap<String, String>) {
+    let cache = LinkCache {
+        rustc_version: rustc_version.to_string(),
+        links: links.clone(),
+    };
+    fs::create_dir_all(cache_dir)
+        .unwrap_or_else(|e| panic!("could not create {}: {e}", cache_dir.display()));
+    let path = cache_dir.join(LINK_CACHE_FILE);
+    let json = serde_json::to_string_pretty(&cache).unwrap();
+    fs::write(&path, json).unwrap_or_else(|e| panic!("could not write {}: {e}", path.display()));
+}
+
+/// Returns the rustdoc version string, used to tag the resolved-link
+/// cache so a toolchain upgrade invalidates it automatically.
+fn rustdoc_version(rustdoc: &str) -> String {
+    let output = Command::new(rustdoc)
+        .arg("--version")
+        .output()
+        .expect("rustdoc installed");
+    String::from_utf8_lossy(&output.stdout).trim().to_string()
+}
+
+/// The Regex used to pull the `a.rs` line:column out of a rustdoc
+/// diagnostic's `--> .../a.rs:LINE:COL` span.
+static RUSTDOC_DIAG_SPAN: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?m)^\s*-->\s*.*[/\\]a\.rs:(\d+):\d+").unwrap());
+
+/// Translates rustdoc's `broken_intra_doc_links` diagnostics (which only
+/// point at lines in the synthetic `a.rs`) back to the real chapter
+/// markdown, and prints a rustdoc-style caret-underlined snippet for
+/// each one so authors can jump straight to the offending link.
+fn report_broken_link_diagnostics(
+    stderr: &str,
+    line_map: &[(u32, &PathBuf, Range<usize>)],
+    chapter_contents: &HashMap<&PathBuf, &str>,
+) {
+    for cap in RUSTDOC_DIAG_SPAN.captures_iter(stderr) {
+        let generated_line: u32 = cap[1].parse().unwrap();
+        let Some((_, ch_path, range)) = line_map.iter().find(|(line, _, _)| *line == generated_line)
+        else {
+            continue;
+        };
+        let Some(contents) = chapter_contents.get(ch_path) else {
+            continue;
+        };
+        print_markdown_snippet(ch_path, contents, range);
+    }
+}
+
+/// Computes the 1-indexed `(line, column)` of byte offset `pos` within
+/// `contents`, by counting newlines up to `pos` and measuring the offset
+/// from the last one.
+fn line_col(contents: &str, pos: usize) -> (usize, usize) {
+    let before = &contents[..pos];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    let col = pos - line_start + 1;
+    (line, col)
+}
+
+#[cfg(test)]
+mod line_col_tests {
+    use super::*;
+
+    #[test]
+    fn first_line_first_column() {
+        assert_eq!(line_col("hello world", 0), (1, 1));
+    }
+
+    #[test]
+    fn mid_first_line() {
+        assert_eq!(line_col("hello world", 6), (1, 7));
+    }
+
+    #[test]
+    fn start_of_second_line() {
+        let contents = "line one\nline two\n";
+        let pos = contents.find("line two").unwrap();
+        assert_eq!(line_col(contents, pos), (2, 1));
+    }
+
+    #[test]
+    fn mid_third_line() {
+        let contents = "one\ntwo\nthree\n";
+        let pos = contents.find("ree").unwrap();
+        assert_eq!(line_col(contents, pos), (3, 3));
+    }
+}
+
+/// Prints a caret-underlined snippet of `contents` at `range`, in the
+/// same style rustdoc itself uses for diagnostics.
+fn print_markdown_snippet(path: &Path, contents: &str, range: &Range<usize>) {
+    let (line_num, col) = line_col(contents, range.start);
+    let line_start = range.start - (col - 1);
+    let line_end = contents[range.start..]
+        .find('\n')
+        .map_or(contents.len(), |i| range.start + i);
+    let line_text = &contents[line_start..line_end];
+    let underline_len = range.end.min(line_end).saturating_sub(range.start).max(1);
+    eprintln!("error: broken link in {}:{line_num}:{col}", path.display());
+    eprintln!("   |");
+    eprintln!("{line_num:>3} | {line_text}");
+    eprintln!("   | {}{}", " ".repeat(col - 1), "^".repeat(underline_len));
+}
+
+/// Walks every chapter looking for bare std paths in code spans (like
+/// `` `std::mem::swap` ``) that were never turned into an actual
+/// intra-doc link, and warns about any that rustdoc can in fact resolve.
+///
+/// This does nothing unless `enabled` is set, since it is a style
+/// suggestion rather than a correctness check; this function reads no
+/// configuration itself, and nothing in this crate calls it. Like
+/// [`std_links`], it checks candidates against the on-disk resolved-link
+/// cache first (a candidate that's already a known-good link elsewhere in
+/// the book obviously resolves), then sends only the still-unknown,
+/// deduped-by-`dest_url` remainder through a single rustdoc invocation
+/// ([`run_rustdoc_lenient`], with the lint set to warn instead of deny
+/// since most candidates are expected not to resolve), and persists those
+/// results to their own disk cache so a later build with the same
+/// unlinked prose doesn't pay for rustdoc again.
+pub fn lint_unlinked_std_paths(book: &Book, enabled: bool) {
+    if !enabled {
+        return;
+    }
+
+    let mut chapter_paths: HashMap<&PathBuf, Vec<Link<'_>>> = HashMap::new();
+    let mut chapter_contents = HashMap::new();
+    for item in book.iter() {
+        let BookItem::Chapter(ch) = item else {
+            continue;
+        };
+        if ch.is_draft_chapter() {
+            continue;
+        }
+        let key = ch.source_path.as_ref().unwrap();
+        let (_, bare_paths) = collect_markdown_links(ch, true);
+        if !bare_paths.is_empty() {
+            chapter_contents.insert(key, ch.content.as_str());
+            chapter_paths.insert(key, bare_paths);
+        }
+    }
+    if chapter_paths.is_empty() {
+        return;
+    }
+
+    let rustdoc = std::env::var("RUSTDOC").unwrap_or_else(|_| "rustdoc".into());
+    let rustc_version = rustdoc_version(&rustdoc);
+    let cache_dir = link_cache_dir();
+    // A candidate already present in `std_links`'s own resolved-link
+    // cache is a known-good std path; there's no need to ask rustdoc
+    // again just to confirm what's already been confirmed.
+    let resolved_links = load_link_cache(&cache_dir, &rustc_version);
+    let mut resolves = load_lint_cache(&cache_dir, &rustc_version);
+
+    let mut residual: HashMap<&str, (&PathBuf, &Link<'_>)> = HashMap::new();
+    for (ch_path, paths) in &chapter_paths {
+        for path in paths {
+            let dest_url = path.dest_url.as_ref();
+            if !resolved_links.contains_key(dest_url) && !resolves.contains_key(dest_url) {
+                residual.entry(dest_url).or_insert((*ch_path, path));
+            }
+        }
+    }
+
+    if !residual.is_empty() {
+        let residual: Vec<_> = residual
+            .into_iter()
+            .map(|(dest_url, (ch_path, path))| (dest_url, ch_path, path))
+            .collect();
+        let newly_checked = run_rustdoc_lenient(&rustdoc, &residual);
+        resolves.extend(newly_checked);
+        save_lint_cache(&cache_dir, &rustc_version, &resolves);
+    }
+
+    for (ch_path, paths) in &chapter_paths {
+        for path in paths {
+            let dest_url = path.dest_url.as_ref();
+            let does_resolve = resolved_links.contains_key(dest_url)
+                || resolves.get(dest_url).copied().unwrap_or(false);
+            if !does_resolve {
+                continue;
+            }
+            let contents = chapter_contents[*ch_path];
+            let (line, col) = line_col(contents, path.range.start);
+            eprintln!(
+                "warning: unlinked standard library path `{}` in {}:{line}:{col}\n\
+                 help: this resolves to the standard library; consider converting it to an intra-doc link",
+                path.dest_url,
+                ch_path.display(),
+            );
+        }
+    }
+}
+
+/// Runs a single, non-denying rustdoc invocation over `residual` (already
+/// deduped by `dest_url` by the caller) and returns whether each
+/// candidate resolved. Unlike [`run_rustdoc`], a candidate failing to
+/// resolve isn't an error here: most bare-path candidates are only
+/// guesses scraped from plain text, and are expected not to resolve.
+fn run_rustdoc_lenient(
+    rustdoc: &str,
+    residual: &[(&str, &PathBuf, &Link<'_>)],
+) -> HashMap<String, bool> {
+    let tmp = TempDir::with_prefix("mdbook-spec-lint-").unwrap();
+    let src_path = tmp.path().join("a.rs");
+    let mut src = String::from("#![warn(rustdoc::broken_intra_doc_links)]\n");
+    for &(dest_url, _, _) in residual {
+        writeln!(src, "//! - LINK: [{dest_url}]").unwrap();
+    }
+    writeln!(
+        src,
+        "extern crate alloc;\n\
+         extern crate proc_macro;\n\
+         extern crate test;\n"
+    )
+    .unwrap();
+    fs::write(&src_path, &src).unwrap();
+
+    let output = Command::new(rustdoc)
+        .arg("--edition=2021")
+        .arg(&src_path)
+        .current_dir(tmp.path())
+        .output()
+        .expect("rustdoc installed");
+    if !output.status.success() {
+        // Shouldn't normally happen since the lint is only a warning;
+        // just treat this run's candidates as unresolved rather than
+        // failing the build over it.
+        return HashMap::new();
+    }
+
+    let generated = fs::read_to_string(tmp.path().join("doc/a/index.html")).unwrap_or_default();
+    let raw_entries: Vec<_> = STD_LINK_EXTRACT_RE
+        .captures_iter(&generated)
+        .map(|cap| cap.get(1).unwrap().as_str())
+        .collect();
+    if raw_entries.len() != residual.len() {
+        return HashMap::new();
+    }
+
+    residual
+        .iter()
+        .zip(raw_entries)
+        .map(|(&(dest_url, _, _), raw_entry)| {
+            // rustdoc renders a resolved intra-doc link as an `<a>` tag,
+            // and an unresolved one as plain text, since the lint is only
+            // a warning here.
+            (dest_url.to_string(), ANCHOR_URL.is_match(raw_entry))
+        })
+        .collect()
+}
+
+/// The file name of the bare-path lint's resolved-or-not cache within
+/// [`link_cache_dir`]. Kept separate from [`LINK_CACHE_FILE`] since,
+/// unlike the main resolved-link cache, it needs to remember negative
+/// results too (a candidate confirmed *not* to resolve is just as worth
+/// caching as one that does).
+const LINT_CACHE_FILE: &str = "mdbook-spec-lint-cache.json";
+
+/// The on-disk format of the bare-path lint's cache. Tagged with the
+/// rustc/rustdoc version string for the same reason as [`LinkCache`].
+#[derive(serde::Serialize, serde::Deserialize, Default)]
+struct LintCache {
+    rustc_version: String,
+    /// Maps a candidate `dest_url` to whether rustdoc resolved it.
+    resolves: HashMap<String, bool>,
+}
+
+/// Loads the bare-path lint's cache from `cache_dir`, discarding it if
+/// it's missing, unreadable, or was generated by a different
+/// `rustc_version`.
+fn load_lint_cache(cache_dir: &Path, rustc_version: &str) -> HashMap<String, bool> {
+    let path = cache_dir.join(LINT_CACHE_FILE);
+    let Ok(contents) = fs::read_to_string(path) else {
+        return HashMap::new();
+    };
+    let Ok(cache) = serde_json::from_str::<LintCache>(&contents) else {
+        return HashMap::new();
+    };
+    if cache.rustc_version != rustc_version {
+        return HashMap::new();
+    }
+    cache.resolves
+}
+
+/// Persists the merged bare-path lint cache to `cache_dir`.
+fn save_lint_cache(cache_dir: &Path, rustc_version: &str, resolves: &HashMap<String, bool>) {
+    let cache = LintCache {
+        rustc_version: rustc_version.to_string(),
+        resolves: resolves.clone(),
+    };
+    fs::create_dir_all(cache_dir)
+        .unwrap_or_else(|e| panic!("could not create {}: {e}", cache_dir.display()));
+    let path = cache_dir.join(LINT_CACHE_FILE);
+    let json = serde_json::to_string_pretty(&cache).unwrap();
+    fs::write(&path, json).unwrap_or_else(|e| panic!("could not write {}: {e}", path.display()));
 }
 
 static DOC_URL: Lazy<Regex> = Lazy::new(|| {
@@ -280,12 +767,58 @@ fn relative_url(url: &str, chapter: &Chapter) -> String {
     }
 }
 
+/// Extracts the `href` target out of the small snippet of HTML that
+/// rustdoc generates for each `LINK:` entry.
+fn extract_anchor_url(raw_html: &str) -> &str {
+    let Some(cap) = ANCHOR_URL.captures(raw_html) else {
+        eprintln!("error: could not find anchor in:\n{raw_html}");
+        process::exit(1);
+    };
+    cap.get(1).unwrap().as_str()
+}
+
+/// One entry in the machine-readable std-link report written when
+/// `SPEC_DUMP_LINKS` is set (see [`write_link_dump`]).
+#[derive(serde::Serialize)]
+struct LinkDumpEntry {
+    /// The chapter's source path, relative to the book's source directory.
+    chapter: PathBuf,
+    /// Where the link pointed to in the original markdown, for example `std::ffi::OsString`.
+    dest_url: String,
+    /// The kind of markdown link (inline, reference, shortcut, etc).
+    link_type: String,
+    /// The final, absolute `doc.rust-lang.org` URL the link was resolved to.
+    resolved_url: String,
+}
+
+/// Writes `entries` to `path` as a JSON array, or as newline-delimited
+/// JSON if `path` has an `.ndjson` extension.
+///
+/// This is a machine-readable audit of every std link the book
+/// resolved, so CI can diff it between commits to catch silent target
+/// drift, and so other tooling can see which std items the reference
+/// links to. Set via the `SPEC_DUMP_LINKS=path.json` environment
+/// variable.
+fn write_link_dump(path: &Path, entries: &[LinkDumpEntry]) {
+    let is_ndjson = path.extension().and_then(|e| e.to_str()) == Some("ndjson");
+    let contents = if is_ndjson {
+        entries
+            .iter()
+            .map(|entry| serde_json::to_string(entry).unwrap())
+            .collect::<Vec<_>>()
+            .join("\n")
+    } else {
+        serde_json::to_string_pretty(entries).unwrap()
+    };
+    fs::write(path, contents).unwrap_or_else(|e| panic!("failed to write {}: {e}", path.display()));
+}
+
 /// Computes the replacements to make in the markdown content.
 ///
 /// Returns a `Vec` of `(md_link, url, range)` where:
 ///
 /// - `md_link` is the markdown link string to show to the user (like `[foo]`).
-/// - `url` is the URL to the standard library.
+/// - `url` is the resolved, absolute URL to the standard library.
 /// - `range` is the range in the original markdown to replace with the new link.
 fn compute_replacements<'a>(
     contents: &'a str,
@@ -294,12 +827,7 @@ fn compute_replacements<'a>(
 ) -> Vec<(&'a str, &'a str, Range<usize>)> {
     let mut replacements = Vec::new();
 
-    for (url, link) in urls.iter().zip(links) {
-        let Some(cap) = ANCHOR_URL.captures(url) else {
-            eprintln!("error: could not find anchor in:\n{url}\nlink={link:#?}");
-            process::exit(1);
-        };
-        let url = cap.get(1).unwrap().as_str();
+    for (&url, link) in urls.iter().zip(links) {
         let md_link = &contents[link.range.clone()];
 
         let range = link.range.clone();
@@ -333,4 +861,465 @@ fn compute_replacements<'a>(
     // Sort and reverse (so that it can replace bottom-up so ranges don't shift).
     replacements.sort_by(|a, b| b.2.clone().partial_cmp(a.2.clone()).unwrap());
     replacements
+}
+
+/// Regex matching a markdown reference-link definition line, like
+/// `` [`OsString`]: std::ffi::OsString ``. pulldown_cmark doesn't expose
+/// the span of these directly, so they're found with a regex instead.
+///
+/// The destination is required to be a single whitespace-free token (or a
+/// `<...>`-wrapped one that may contain spaces), with an optional quoted or
+/// parenthesized title after it, per the CommonMark link reference
+/// definition grammar. This is what distinguishes a real definition like
+/// `` [`OsString`]: std::ffi::OsString `` from a prose line that merely
+/// starts with `[something]:`, such as `[Trait]: a set of methods types
+/// can implement.`, which this regex must not match.
+static REF_DEFINITION_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"(?m)^[ \t]*(\[[^\]]+\]):[ \t]*(?:<[^<>\n]*>|\S+)(?:[ \t]+(?:"[^"]*"|'[^']*'|\([^()]*\)))?[ \t]*$"#,
+    )
+    .unwrap()
+});
+
+/// Regex matching any markdown `[...]` bracketed span, used to find label
+/// references when checking whether a reference definition is still used.
+static BRACKET_SPAN_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\[[^\]]+\]").unwrap());
+
+/// Normalizes a markdown reference label for comparison, per the
+/// CommonMark rule that labels are matched case-insensitively with
+/// runs of whitespace collapsed.
+fn normalize_label(label: &str) -> String {
+    label.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase()
+}
+
+/// Extracts the reference label a (non-inline) link relies on: for
+/// `[text][label]` it's `label`; for the collapsed `[text][]` or
+/// shortcut `[text]` forms, the label defaults to `text` itself.
+fn link_label<'a>(link: &Link<'_>, contents: &'a str) -> Option<&'a str> {
+    let md_link = &contents[link.range.clone()];
+    match link.link_type {
+        LinkType::Reference | LinkType::Collapsed => {
+            let cap = MD_LINK_REFERENCE.captures(md_link)?;
+            let text = strip_brackets(cap.get(1)?.as_str());
+            let label = strip_brackets(cap.get(2)?.as_str());
+            Some(if label.is_empty() { text } else { label })
+        }
+        LinkType::Shortcut => {
+            let cap = MD_LINK_SHORTCUT.captures(md_link)?;
+            Some(strip_brackets(cap.get(1)?.as_str()))
+        }
+        _ => None,
+    }
+}
+
+/// Returns whether `label` (already normalized via [`normalize_label`]) is
+/// still used by a link in `contents` other than the ones being rewritten
+/// in `replaced_links` — for example a non-std link that happens to share
+/// the same reference label.
+///
+/// This is a conservative, syntax-level check: it looks for a `[...]` span
+/// anywhere outside of `definition_range` and the spans of
+/// `replaced_links` whose contents normalize to `label`, rather than
+/// re-parsing the document. Each candidate span is normalized before
+/// comparing, so labels that merely differ in whitespace (e.g. `[My
+/// Label]` vs. `` [My   Label] ``) are still recognized as the same label,
+/// per CommonMark's label-matching rule.
+fn other_link_uses_label(
+    contents: &str,
+    label: &str,
+    replaced_links: &[Link<'_>],
+    definition_range: Range<usize>,
+) -> bool {
+    BRACKET_SPAN_RE.find_iter(contents).any(|m| {
+        let range = m.range();
+        if definition_range.start <= range.start && range.start < definition_range.end {
+            return false;
+        }
+        if replaced_links
+            .iter()
+            .any(|link| link.range.start <= range.start && range.end <= link.range.end)
+        {
+            return false;
+        }
+        normalize_label(strip_brackets(m.as_str())) == label
+    })
+}
+
+/// Finds reference-link definitions in `contents` that only existed to
+/// support links in `replaced_links` (all of which are being rewritten
+/// to inline links), and returns their byte ranges so they can be
+/// deleted along with the link rewrites.
+fn orphaned_reference_definitions(
+    contents: &str,
+    replaced_links: &[Link<'_>],
+) -> Vec<Range<usize>> {
+    let replaced_labels: HashSet<String> = replaced_links
+        .iter()
+        .filter_map(|link| link_label(link, contents))
+        .map(normalize_label)
+        .collect();
+    if replaced_labels.is_empty() {
+        return Vec::new();
+    }
+
+    let mut orphaned = Vec::new();
+    for cap in REF_DEFINITION_RE.captures_iter(contents) {
+        let whole = cap.get(0).unwrap().range();
+        let label = strip_brackets(cap.get(1).unwrap().as_str());
+        let label = normalize_label(label);
+        if !replaced_labels.contains(&label) {
+            continue;
+        }
+        if other_link_uses_label(contents, &label, replaced_links, whole.clone()) {
+            continue;
+        }
+        // Also eat the trailing newline so rewriting doesn't leave a blank line behind.
+        let mut range = whole;
+        if contents[range.end..].starts_with('\n') {
+            range.end += 1;
+        }
+        orphaned.push(range);
+    }
+    orphaned
+}
+
+/// Strips a single layer of surrounding `[` and `]` from `s`, if present.
+fn strip_brackets(s: &str) -> &str {
+    s.strip_prefix('[').and_then(|s| s.strip_suffix(']')).unwrap_or(s)
+}
+
+#[cfg(test)]
+mod orphaned_reference_definitions_tests {
+    use super::*;
+
+    fn shortcut_link<'a>(contents: &'a str, text: &str, dest_url: &'a str) -> Link<'a> {
+        let bracketed = format!("[{text}]");
+        let start = contents.find(&bracketed).unwrap();
+        Link {
+            link_type: LinkType::Shortcut,
+            dest_url: CowStr::Borrowed(dest_url),
+            range: start..start + bracketed.len(),
+        }
+    }
+
+    fn reference_link<'a>(
+        contents: &'a str,
+        text: &str,
+        label: &str,
+        dest_url: &'a str,
+    ) -> Link<'a> {
+        let full = format!("[{text}][{label}]");
+        let start = contents.find(&full).unwrap();
+        Link {
+            link_type: LinkType::Reference,
+            dest_url: CowStr::Borrowed(dest_url),
+            range: start..start + full.len(),
+        }
+    }
+
+    #[test]
+    fn deletes_a_real_orphaned_definition() {
+        let contents = "\
+[`OsString`] converts to a std type.
+
+[`OsString`]: std::ffi::OsString
+";
+        let link = shortcut_link(contents, "`OsString`", "std::ffi::OsString");
+        let orphaned = orphaned_reference_definitions(contents, &[link]);
+        assert_eq!(orphaned.len(), 1);
+        let def_start = contents.find("[`OsString`]: std::ffi::OsString").unwrap();
+        assert_eq!(orphaned[0].start, def_start);
+    }
+
+    #[test]
+    fn does_not_delete_a_prose_line_that_merely_starts_with_bracket_colon() {
+        // `[Trait]:` here is not a CommonMark reference-link definition:
+        // its "destination" is several words of prose, not a single
+        // token. The label also happens to normalize to the same label
+        // as the real `[Trait]` link below, which used to be enough to
+        // get it wrongly deleted before the regex was tightened.
+        let contents = "\
+[Trait] is linked elsewhere.
+
+[Trait]: a set of methods types can implement.
+";
+        let link = shortcut_link(contents, "Trait", "std::ops::Add");
+        let orphaned = orphaned_reference_definitions(contents, &[link]);
+        assert!(
+            orphaned.is_empty(),
+            "prose line must not be treated as a definition: {orphaned:?}"
+        );
+    }
+
+    #[test]
+    fn keeps_a_definition_still_used_by_another_link_with_extra_whitespace() {
+        // `[std text][My Label]` is the link being rewritten (so its
+        // definition is a deletion candidate), but a second, unrelated
+        // link still references the same label with extra internal
+        // whitespace (`[My   Label]` instead of `[My Label]`), which
+        // CommonMark treats as the same label. `other_link_uses_label`
+        // must normalize whitespace on both sides to see that and save
+        // the definition.
+        let contents = "\
+[std text][My Label] and [other text][My   Label] too.
+
+[My Label]: std::ops::Add
+";
+        let link = reference_link(contents, "std text", "My Label", "std::ops::Add");
+        let orphaned = orphaned_reference_definitions(contents, &[link]);
+        assert!(
+            orphaned.is_empty(),
+            "[My Label] is still used (with extra whitespace) elsewhere, \
+             so it must not be deleted: {orphaned:?}"
+        );
+    }
+
+    #[test]
+    fn other_link_uses_label_normalizes_whitespace_in_the_haystack() {
+        let contents =
+            "[foo][My   Label] and here is the def.\n\n[My Label]: https://example.com\n";
+        let def_start = contents.find("[My Label]:").unwrap();
+        let def_end = contents[def_start..].find('\n').map_or(contents.len(), |i| def_start + i);
+        assert!(other_link_uses_label(contents, "my label", &[], def_start..def_end));
+    }
+}
+
+/// The Regex used to extract `href` attributes from rendered HTML.
+static HREF_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r#"href="([^"]*)""#).unwrap());
+/// The Regex used to extract `id` attributes from rendered HTML.
+static ID_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r#"id="([^"]*)""#).unwrap());
+
+/// A link that could not be resolved during [`validate_links`].
+#[derive(Debug, Clone)]
+pub struct BrokenPageLink {
+    /// The HTML file that contains the link, as found while walking the
+    /// output root (so the path includes the root).
+    pub page: PathBuf,
+    /// The raw `href` value that failed to resolve.
+    pub href: String,
+}
+
+/// Walks every `.html` file under `root` (the final rendered book output)
+/// and verifies that every relative link resolves to a file that exists,
+/// and that any `#fragment` resolves to an element with that `id` in the
+/// target file.
+///
+/// This is modeled on the linkchecker used for rustc's documentation
+/// build: rather than stopping at the first problem, every broken link is
+/// collected and reported together. `exceptions` is a list of
+/// `(page_glob, &[href])` pairs for links that are known to be broken and
+/// should not fail the check (for example, links into docs that aren't
+/// vendored for offline validation); `page_glob` is matched against the
+/// page's path relative to `root` with `/` separators, where `*` matches
+/// any run of characters.
+///
+/// On success, prints a summary count and returns the number of files
+/// checked. If any non-excepted broken links are found, they are all
+/// printed to stderr and the process exits with an error, so this can be
+/// used to gate CI.
+pub fn validate_links(root: &Path, exceptions: &[(&str, &[&str])]) -> usize {
+    let mut html_files = Vec::new();
+    collect_html_files(root, &mut html_files);
+
+    let mut broken = Vec::new();
+    for page in &html_files {
+        let contents = fs::read_to_string(page)
+            .unwrap_or_else(|e| panic!("could not read {}: {e}", page.display()));
+        for cap in HREF_RE.captures_iter(&contents) {
+            let href = cap.get(1).unwrap().as_str();
+            if href.is_empty()
+                || href.starts_with("http://")
+                || href.starts_with("https://")
+                || href.starts_with("mailto:")
+            {
+                continue;
+            }
+            if let Some(link) = check_href(page, href) {
+                broken.push(link);
+            }
+        }
+    }
+
+    let total = html_files.len();
+    broken.retain(|link| {
+        let key = page_key(root, &link.page);
+        !exceptions
+            .iter()
+            .any(|(glob, hrefs)| glob_match(glob, &key) && hrefs.contains(&link.href.as_str()))
+    });
+
+    if !broken.is_empty() {
+        for link in &broken {
+            eprintln!("error: broken link `{}` in {}", link.href, link.page.display());
+        }
+        eprintln!(
+            "error: found {} broken link(s) across {total} checked file(s)",
+            broken.len()
+        );
+        process::exit(1);
+    }
+
+    eprintln!("linkcheck: {total} file(s) checked, no broken links found");
+    total
+}
+
+/// Recursively collects every `.html` file under `dir`.
+fn collect_html_files(dir: &Path, out: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_html_files(&path, out);
+        } else if path.extension().and_then(|e| e.to_str()) == Some("html") {
+            out.push(path);
+        }
+    }
+}
+
+/// Checks whether a single `href` found on `page` resolves to an existing
+/// file (and, if it has a fragment, an existing `id` within that file).
+fn check_href(page: &Path, href: &str) -> Option<BrokenPageLink> {
+    let (path_part, fragment) = match href.split_once('#') {
+        Some((p, f)) => (p, Some(f)),
+        None => (href, None),
+    };
+    let dir = page.parent().unwrap();
+    let target = if path_part.is_empty() {
+        page.to_path_buf()
+    } else {
+        normalize_relative_path(dir, &percent_decode(path_part))
+    };
+    if !target.is_file() {
+        return Some(BrokenPageLink {
+            page: page.to_path_buf(),
+            href: href.to_string(),
+        });
+    }
+    if let Some(fragment) = fragment {
+        if !fragment.is_empty() {
+            let contents = fs::read_to_string(&target).unwrap_or_default();
+            let has_id = ID_RE
+                .captures_iter(&contents)
+                .any(|cap| cap.get(1).unwrap().as_str() == fragment);
+            if !has_id {
+                return Some(BrokenPageLink {
+                    page: page.to_path_buf(),
+                    href: href.to_string(),
+                });
+            }
+        }
+    }
+    None
+}
+
+/// Resolves a relative href path against the directory containing the
+/// page that referenced it, collapsing `.` and `..` components.
+fn normalize_relative_path(base_dir: &Path, href_path: &str) -> PathBuf {
+    let mut components: Vec<std::ffi::OsString> =
+        base_dir.iter().map(|c| c.to_os_string()).collect();
+    for part in href_path.split('/') {
+        match part {
+            "" | "." => {}
+            ".." => {
+                components.pop();
+            }
+            _ => components.push(part.into()),
+        }
+    }
+    components.iter().collect()
+}
+
+/// Percent-decodes a URL path component (e.g. `%20` to a space).
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        // Decode the two hex digits directly off the bytes, without ever
+        // slicing `s` into a `&str`: `i + 1`/`i + 2` are not guaranteed to
+        // land on UTF-8 char boundaries (a stray `%` next to a multi-byte
+        // character, e.g. an em dash from `ENABLE_SMART_PUNCTUATION`,
+        // would otherwise panic).
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            let hi = (bytes[i + 1] as char).to_digit(16);
+            let lo = (bytes[i + 2] as char).to_digit(16);
+            if let (Some(hi), Some(lo)) = (hi, lo) {
+                out.push((hi * 16 + lo) as u8);
+                i += 3;
+                continue;
+            }
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// Returns `page`'s path relative to `root`, using `/` separators.
+fn page_key(root: &Path, page: &Path) -> String {
+    page.strip_prefix(root)
+        .unwrap_or(page)
+        .iter()
+        .map(|c| c.to_string_lossy().into_owned())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Matches `path` against a glob pattern where `*` matches any run of characters.
+fn glob_match(glob: &str, path: &str) -> bool {
+    let pattern = format!("^{}$", regex::escape(glob).replace("\\*", ".*"));
+    Regex::new(&pattern).unwrap().is_match(path)
+}
+
+#[cfg(test)]
+mod validate_links_tests {
+    use super::*;
+
+    #[test]
+    fn percent_decode_decodes_escapes() {
+        assert_eq!(percent_decode("a%20b"), "a b");
+        assert_eq!(percent_decode("no-escapes"), "no-escapes");
+    }
+
+    #[test]
+    fn percent_decode_leaves_invalid_escapes_alone() {
+        assert_eq!(percent_decode("100%"), "100%");
+        assert_eq!(percent_decode("100%zz"), "100%zz");
+    }
+
+    #[test]
+    fn percent_decode_does_not_panic_near_multibyte_chars() {
+        // Regression test: a '%' within two bytes of a multi-byte
+        // character used to be decoded by slicing `&s[i + 1..i + 3]`,
+        // which isn't guaranteed to land on a UTF-8 char boundary and
+        // panicked instead of being reported as a broken link.
+        assert_eq!(percent_decode("%€x"), "%€x");
+    }
+
+    #[test]
+    fn normalize_relative_path_resolves_dot_dot() {
+        let base = Path::new("book/foo/bar");
+        assert_eq!(
+            normalize_relative_path(base, "../baz.html"),
+            Path::new("book/foo/baz.html")
+        );
+    }
+
+    #[test]
+    fn normalize_relative_path_ignores_dot_components() {
+        let base = Path::new("book/foo");
+        assert_eq!(
+            normalize_relative_path(base, "./bar.html"),
+            Path::new("book/foo/bar.html")
+        );
+    }
+
+    #[test]
+    fn glob_match_star_matches_any_run_of_characters() {
+        assert!(glob_match("foo/*.html", "foo/bar.html"));
+        assert!(glob_match("foo/*.html", "foo/bar/baz.html"));
+        assert!(!glob_match("foo/*.html", "other/bar.html"));
+    }
 }
\ No newline at end of file